@@ -0,0 +1,40 @@
+use std::path::Path;
+
+pub mod xonsh;
+
+pub trait Shell {
+    fn activate(&self, exe: &Path) -> String;
+    fn deactivate(&self, exe: &Path) -> String;
+    fn set_env(&self, k: &str, v: &str) -> String;
+    fn unset_env(&self, k: &str) -> String;
+    fn set_path(&self, k: &str, paths: &[String]) -> String;
+    fn add_path(&self, k: &str, v: &str) -> String;
+    fn remove_path(&self, k: &str, v: &str) -> String;
+
+    /// Python statements that set `var` to a copy of the current environment with FORCE_COLOR/NO_COLOR
+    /// added based on whether the parent shell's stdout is a TTY, for shells that hand `var` to a hook
+    /// subprocess via `env=`. `indent` is prepended to every line so it can be spliced into the caller's
+    /// template at whatever depth it's generated at. Default-provided so every python-based hook
+    /// integration (currently just Xonsh) forwards TTY/color state the same way instead of reinventing it.
+    fn hook_color_env_py(&self, var: &str, indent: &str) -> String {
+        format!(
+            "{indent}{var} = dict(environ)\n{indent}{var}['FORCE_COLOR' if sys.stdout.isatty() else 'NO_COLOR'] = '1'\n"
+        )
+    }
+}
+
+pub fn is_dir_in_path(dir: &Path) -> bool {
+    match std::env::var_os("PATH") {
+        Some(paths) => std::env::split_paths(&paths).any(|p| paths_eq(&p, dir)),
+        None => false,
+    }
+}
+
+fn paths_eq(a: &Path, b: &Path) -> bool {
+    if cfg!(windows) {
+        a.to_string_lossy()
+            .eq_ignore_ascii_case(&b.to_string_lossy())
+    } else {
+        a == b
+    }
+}
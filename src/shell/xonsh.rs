@@ -36,10 +36,38 @@ fn xonsh_escape_char(ch: char) -> Option<&'static str> {
     }
 }
 
+// env var names aren't unix shell tokens, so don't run them through shell_escape::unix::escape (which
+// quotes/escapes for a POSIX shell and misbehaves on Windows, where xonsh also runs); just guard the
+// characters that would break out of the python string literal we embed the key into
+fn xonsh_escape_key(k: &str) -> Cow<str> {
+    xonsh_escape_sq(k)
+}
+
+// shared by activate() (idempotent re-source must not stack a duplicate handler) and deactivate()
+fn remove_listen_prompt_py() -> String {
+    formatdoc! {r#"
+        hooks = {{
+          'on_pre_prompt' : ['listen_prompt'],
+        }}
+        for   hook_type in hooks:
+          hook_fns = hooks[hook_type]
+          for hook_fn   in hook_fns:
+            hndl = getattr(XSH.builtins.events, hook_type)
+            for fn in hndl:
+              if fn.__name__ == hook_fn:
+                hndl.remove(fn)
+                break
+    "#}
+}
+
 impl Shell for Xonsh {
     fn activate(&self, exe: &Path) -> String {
         let dir = exe.parent().unwrap();
-        let exe = exe.display();
+        // single-quoted + escaped like every other interpolated string here, not a bare double-quoted
+        // literal: exe is a filesystem path and on Windows may contain backslashes that would otherwise
+        // be interpreted as python escapes
+        let exe = xonsh_escape_sq(&exe.display().to_string()).into_owned();
+        let version = env!("CARGO_PKG_VERSION");
         let mut out = String::new();
 
         // todo: xonsh doesn't update the environment that rtx relies on with $PATH.add even with $UPDATE_OS_ENVIRON (github.com/xonsh/xonsh/issues/3207)
@@ -47,27 +75,49 @@ impl Shell for Xonsh {
         // meanwhile, save variables twice: in shell env + in os env
         // use xonsh API instead of $.xsh to allow use inside of .py configs, which start faster due to being compiled to .pyc
         out.push_str(&formatdoc! {r#"
-            import sys, subprocess
+            import sys, os, subprocess, threading
             from os               import environ
             from xonsh.built_ins  import XSH
 
         "#});
         if !is_dir_in_path(dir) {
             let dir_str = dir.to_string_lossy();
-            let dir_esc = xonsh_escape_sq(&dir_str);
-            out.push_str(&formatdoc! {r#"
-                envx = XSH.env
-                envx['PATH'].add('{dir_esc}')
-                environ['PATH'] = envx.get_detyped('PATH')
-
-            "#});
+            out.push_str(&self.add_path("PATH", &dir_str));
+            out.push('\n');
         }
-        // todo: subprocess instead of $() is a bit faster, but lose auto-color detection (use $FORCE_COLOR)
+        // re-sourcing this script (e.g. a .xonshrc reload) must not stack a second listen_prompt; drop
+        // whatever's already registered (possibly from an older rtx binary) before installing the current one
+        out.push_str(&remove_listen_prompt_py());
+        out.push('\n');
+
+        // subprocess instead of $() is a bit faster, but loses xonsh's automatic color detection, so
+        // forward the parent shell's TTY state explicitly (see Shell::hook_color_env_py)
+        let hook_color_env = self.hook_color_env_py("hook_env", "  ");
         out.push_str(&formatdoc! {r#"
+            __rtx_version = '{version}'
+
             def listen_prompt(): # Hook Events
               ctx = XSH.ctx
 
-              rtx_hook_proc  = subprocess.run(["{exe}",'hook-env','-s','xonsh'],capture_output=True)
+            {hook_color_env}
+              # `{exe} --version` prints e.g. "rtx 1.2.3" (clap's name+version format), while __rtx_version
+              # is the bare semver rtx was activated with; compare like-for-like by taking the last token
+              cur_version_out = subprocess.run(['{exe}','--version'],capture_output=True,env=hook_env).stdout.decode().strip()
+              cur_version     = cur_version_out.rsplit(' ', 1)[-1] if cur_version_out else ''
+              if cur_version and cur_version != ctx.get('__rtx_version'):
+                # binary was upgraded since this hook was installed: re-run activation to refresh the hook,
+                # then fall through and still run hook-env below so tools update this same prompt. listen_prompt
+                # is executing from inside on_pre_prompt's own fire loop right now, so registering/removing a
+                # handler on that same event here would mutate its handler set mid-iteration (xonsh's Event
+                # fires by iterating its handler set directly, which raises RuntimeError if changed under it);
+                # deferring via a timer lets the fire loop return first, so the reactivation runs cleanly after
+                def _rtx_reactivate():
+                  reactivate = subprocess.run(['{exe}','activate','xonsh'],capture_output=True,env=hook_env).stdout
+                  if reactivate:
+                    execx(reactivate.decode(), 'exec', ctx, filename='rtx')
+                threading.Timer(0, _rtx_reactivate).start()
+
+              rtx_hook_proc  = subprocess.run(['{exe}','hook-env','-s','xonsh'],capture_output=True,env=hook_env)
               rtx_hook       = rtx_hook_proc.stdout
               rtx_hook_err   = rtx_hook_proc.stderr
 
@@ -76,32 +126,28 @@ impl Shell for Xonsh {
               if rtx_hook:
                 execx(rtx_hook.decode(), 'exec', ctx, filename='rtx')
 
+            XSH.ctx['__rtx_version'] = __rtx_version
             XSH.builtins.events.on_pre_prompt(listen_prompt) # Activate hook: before showing the prompt
             "#});
 
         out
     }
 
-    fn deactivate(&self) -> String {
-        formatdoc! {r#"
+    fn deactivate(&self, exe: &Path) -> String {
+        let dir = exe.parent().unwrap();
+        let dir_str = dir.to_string_lossy();
+        let mut out = formatdoc! {r#"
             from xonsh.built_ins  import XSH
 
-            hooks = {{
-              'on_pre_prompt' : ['listen_prompt'],
-            }}
-            for   hook_type in hooks:
-              hook_fns = hooks[hook_type]
-              for hook_fn   in hook_fns:
-                hndl = getattr(XSH.builtins.events, hook_type)
-                for fn in hndl:
-                  if fn.__name__ == hook_fn:
-                    hndl.remove(fn)
-                    break
-        "#}
+        "#};
+        out.push_str(&remove_listen_prompt_py());
+        out.push('\n');
+        // undo exactly what activate() added to PATH, rather than leaving rtx's dir behind
+        out.push_str(&self.remove_path("PATH", &dir_str));
+        out
     }
 
     fn set_env(&self, k: &str, v: &str) -> String {
-        let k = shell_escape::unix::escape(k.into()); // todo: drop illegal chars, not escape?
         formatdoc!(
             r#"
             from os               import environ
@@ -111,7 +157,7 @@ impl Shell for Xonsh {
             envx[   '{k}'] = '{v}'
             environ['{k}'] = envx.get_detyped('{k}')
         "#,
-            k = shell_escape::unix::escape(k), // todo: drop illegal chars, not escape?
+            k = xonsh_escape_key(k),
             v = xonsh_escape_sq(v)
         )
     }
@@ -126,7 +172,64 @@ impl Shell for Xonsh {
             envx.pop[   '{k}',None]
             environ.pop['{k}',None]
         "#,
-            k = shell_escape::unix::escape(k.into()) // todo: drop illegal chars, not escape?
+            k = xonsh_escape_key(k)
+        )
+    }
+
+    // xonsh models PATH-like vars as a typed EnvPath list (see xonsh's is_env_path/env_path_to_str/detype),
+    // so assign a list (set_path) or push/pop individual entries (add_path/remove_path) instead of flattening the
+    // whole variable to a string like set_env/unset_env do
+    fn set_path(&self, k: &str, paths: &[String]) -> String {
+        let items = paths
+            .iter()
+            .map(|p| format!("'{}'", xonsh_escape_sq(p)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        formatdoc!(
+            r#"
+            from os               import environ
+            from xonsh.built_ins  import XSH
+
+            envx = XSH.env
+            envx[   '{k}'] = [{items}]
+            environ['{k}'] = envx.get_detyped('{k}')
+        "#,
+            k = xonsh_escape_key(k),
+            items = items
+        )
+    }
+
+    fn add_path(&self, k: &str, v: &str) -> String {
+        formatdoc!(
+            r#"
+            from os               import environ
+            from xonsh.built_ins  import XSH
+
+            envx = XSH.env
+            envx[   '{k}'].add('{v}')
+            environ['{k}'] = envx.get_detyped('{k}')
+        "#,
+            k = xonsh_escape_key(k),
+            v = xonsh_escape_sq(v)
+        )
+    }
+
+    fn remove_path(&self, k: &str, v: &str) -> String {
+        // os.path.normcase is a no-op on unix and lower-cases + normalizes slashes on windows, so this
+        // compares the way xonsh's own ON_WINDOWS-aware path handling does instead of assuming unix semantics
+        formatdoc!(
+            r#"
+            import os
+            from os               import environ
+            from xonsh.built_ins  import XSH
+
+            envx    = XSH.env
+            rm_norm = os.path.normcase('{v}')
+            envx[   '{k}'] = type(envx['{k}'])(p for p in envx['{k}'] if os.path.normcase(p) != rm_norm)
+            environ['{k}'] = envx.get_detyped('{k}')
+        "#,
+            k = xonsh_escape_key(k),
+            v = xonsh_escape_sq(v)
         )
     }
 }
@@ -149,4 +252,24 @@ mod tests {
     fn test_unset_env() {
         insta::assert_snapshot!(Xonsh::default().unset_env("FOO"));
     }
+
+    #[test]
+    fn test_set_path() {
+        insta::assert_snapshot!(Xonsh::default().set_path("PATH", &["/foo".into(), "/bar".into()]));
+    }
+
+    #[test]
+    fn test_add_path() {
+        insta::assert_snapshot!(Xonsh::default().add_path("PATH", "/foo"));
+    }
+
+    #[test]
+    fn test_remove_path() {
+        insta::assert_snapshot!(Xonsh::default().remove_path("PATH", "/foo"));
+    }
+
+    #[test]
+    fn test_deactivate() {
+        insta::assert_snapshot!(Xonsh::default().deactivate(Path::new("/some/dir/rtx")));
+    }
 }